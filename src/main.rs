@@ -1,6 +1,6 @@
 use anyhow::*;
 use clap::Parser;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fs};
 
 #[derive(Parser, Debug)]
@@ -24,9 +24,33 @@ struct Args {
     /// Previously taken augments (comma-separated)
     #[arg(long, default_value="")]
     taken: String,
-    /// Data dir containing YAMLs
+    /// Data dir containing YAMLs (used when --source local)
     #[arg(long, default_value=".")]
     data_dir: String,
+    /// Output format
+    #[arg(long, value_enum, default_value_t=Format::Text)]
+    format: Format,
+    /// Where to load augments/traits/items/config from
+    #[arg(long, value_enum, default_value_t=Source::Local)]
+    source: Source,
+    /// Base URL to fetch data from (required when --source http)
+    #[arg(long)]
+    base_url: Option<String>,
+    /// Fail instead of warning on unknown augment names or unresolved tags
+    #[arg(long, default_value_t=false)]
+    strict: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Source {
+    Local,
+    Http,
 }
 
 #[derive(Deserialize)]
@@ -52,12 +76,32 @@ struct TraitGroup {
 #[derive(Deserialize)]
 struct ItemsDb {
     components: HashMap<String, i32>,
+    /// Per-augment item-slam scoring rules, keyed by augment name (lowercase).
+    /// Replaces hardcoded augment lists in `item_slam_bonus`.
+    #[serde(default)]
+    slam_rules: HashMap<String, SlamRule>,
+}
+
+#[derive(Deserialize)]
+struct SlamRule {
+    parts: Vec<SlamPart>,
+    divisor: f32,
+}
+#[derive(Deserialize)]
+struct SlamPart {
+    component: String,
+    weight: f32,
 }
 
 #[derive(Deserialize)]
 struct Config {
     weights: Weights,
     trait_breakpoints: HashMap<i32, i32>,
+    /// Optional arithmetic expression overriding the hardcoded multiplier formula,
+    /// e.g. `1 + W_TRAIT*f_trait + W_ITEMS*f_items + max(f_hp, f_stage) - W_CONFLICT*f_conf`.
+    /// Falls back to the built-in formula when absent.
+    #[serde(default)]
+    formula: Option<String>,
 }
 #[derive(Deserialize)]
 struct Weights {
@@ -69,48 +113,139 @@ struct Weights {
     W_SYNERGY: f32,
 }
 
+/// Where `Augments`, `TraitsDb`, `ItemsDb` and `Config` are loaded from.
+/// `score_one` and the rest of the pipeline only ever see the typed structs,
+/// so swapping the backend doesn't touch any scoring logic.
+trait DataSource {
+    fn augments(&self) -> Result<Augments>;
+    fn traits(&self) -> Result<TraitsDb>;
+    fn items(&self) -> Result<ItemsDb>;
+    fn config(&self) -> Result<Config>;
+}
+
+struct YamlDataSource {
+    data_dir: String,
+}
+
+impl DataSource for YamlDataSource {
+    fn augments(&self) -> Result<Augments> { load_yaml(format!("{}/augments.yaml", self.data_dir)) }
+    fn traits(&self) -> Result<TraitsDb> { load_yaml(format!("{}/traits.yaml", self.data_dir)) }
+    fn items(&self) -> Result<ItemsDb> { load_yaml(format!("{}/items.yaml", self.data_dir)) }
+    fn config(&self) -> Result<Config> { load_yaml(format!("{}/config.yaml", self.data_dir)) }
+}
+
+/// Fetches the same four documents from a live backend instead of local files,
+/// so augment base scores and trait breakpoints can track the current patch
+/// without editing YAML on disk.
+struct HttpDataSource {
+    base_url: String,
+}
+
+impl DataSource for HttpDataSource {
+    fn augments(&self) -> Result<Augments> { fetch_yaml(&format!("{}/augments.yaml", self.base_url)) }
+    fn traits(&self) -> Result<TraitsDb> { fetch_yaml(&format!("{}/traits.yaml", self.base_url)) }
+    fn items(&self) -> Result<ItemsDb> { fetch_yaml(&format!("{}/items.yaml", self.base_url)) }
+    fn config(&self) -> Result<Config> { fetch_yaml(&format!("{}/config.yaml", self.base_url)) }
+}
+
+fn fetch_yaml<T: for<'de> serde::Deserialize<'de>>(url: &str) -> Result<T> {
+    let s = reqwest::blocking::get(url)
+        .with_context(|| format!("Failed to fetch {}", url))?
+        .error_for_status()
+        .with_context(|| format!("Non-success response fetching {}", url))?
+        .text()
+        .with_context(|| format!("Failed to read response body from {}", url))?;
+    let val = serde_yaml::from_str::<T>(&s)
+        .with_context(|| format!("Failed to parse YAML from {}", url))?;
+    Ok(val)
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    let aug: Augments = load_yaml(&format!("{}/augments.yaml", args.data_dir))?;
-    let traits_db: TraitsDb = load_yaml(&format!("{}/traits.yaml", args.data_dir))?;
-    let items_db: ItemsDb = load_yaml(&format!("{}/items.yaml", args.data_dir))?;
-    let cfg: Config = load_yaml(&format!("{}/config.yaml", args.data_dir))?;
+    let source: Box<dyn DataSource> = match args.source {
+        Source::Local => Box::new(YamlDataSource { data_dir: args.data_dir.clone() }),
+        Source::Http => {
+            let base_url = args.base_url.clone()
+                .context("--base-url is required when --source http is selected")?;
+            Box::new(HttpDataSource { base_url })
+        }
+    };
+
+    let aug: Augments = source.augments()?;
+    let traits_db: TraitsDb = source.traits()?;
+    let items_db: ItemsDb = source.items()?;
+    let cfg: Config = source.config()?;
 
     let offered: Vec<String> = split_csv(&args.augments);
     let taken = split_csv(&args.taken);
     let traits = parse_kv(&args.traits);
     let parts = parse_kv(&args.parts);
 
+    let mut warnings = Vec::new();
+    warnings.extend(validate_augment_names("--augments", &offered, &aug));
+    warnings.extend(validate_augment_names("--taken", &taken, &aug));
+    warnings.extend(validate_tags(&aug, &traits_db));
+    if !warnings.is_empty() {
+        if args.strict {
+            bail!("Validation failed:\n{}", warnings.join("\n"));
+        }
+        for w in &warnings {
+            eprintln!("warning: {}", w);
+        }
+    }
+
     let mut scored = Vec::new();
     for a in offered {
         let (score, mult, detail) = score_one(
             &a, &aug, &traits_db, &items_db, &cfg,
             &args.stage, args.hp, &traits, &parts, &taken
-        );
+        )?;
         scored.push((a, score, mult, detail));
     }
     scored.sort_by(|x,y| y.1.partial_cmp(&x.1).unwrap());
 
-    println!("Recommended order:\n");
-    for (i,(name, score, mult, d)) in scored.iter().enumerate() {
-        println!("{}. {}: {:.1}", i+1, name, score);
-        println!("   base={:.1} x mult={:.3}", d.base, mult);
-        println!("   reasons:");
-        if d.f_trait != 0.0 { println!("     • Trait proximity: {:.2}", d.f_trait); }
-        if d.f_items != 0.0 { println!("     • Item slam: {:.2}", d.f_items); }
-        if d.f_stage != 0.0 { println!("     • Stage urgency: {:.2}", d.f_stage); }
-        if d.f_hp    != 0.0 { println!("     • HP danger: {:.2}", d.f_hp); }
-        if d.f_syn   != 0.0 { println!("     • Synergy tags: {:.2}", d.f_syn); }
-        if d.f_conf  != 0.0 { println!("     • Conflict (penalty): {:.2}", d.f_conf); }
-        println!();
+    match args.format {
+        Format::Json => {
+            let out: Vec<ScoredOutput> = scored.iter().map(|(name, score, mult, d)| ScoredOutput {
+                augment: name,
+                score: *score,
+                mult: *mult,
+                detail: d,
+            }).collect();
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        }
+        Format::Text => {
+            println!("Recommended order:\n");
+            for (i,(name, score, mult, d)) in scored.iter().enumerate() {
+                println!("{}. {}: {:.1}", i+1, name, score);
+                println!("   base={:.1} x mult={:.3}", d.base, mult);
+                println!("   reasons:");
+                if d.f_trait != 0.0 { println!("     • Trait proximity: {:.2}", d.f_trait); }
+                if d.f_items != 0.0 { println!("     • Item slam: {:.2}", d.f_items); }
+                if d.f_stage != 0.0 { println!("     • Stage urgency: {:.2}", d.f_stage); }
+                if d.f_hp    != 0.0 { println!("     • HP danger: {:.2}", d.f_hp); }
+                if d.f_syn   != 0.0 { println!("     • Synergy tags: {:.2}", d.f_syn); }
+                if d.f_conf  != 0.0 { println!("     • Conflict (penalty): {:.2}", d.f_conf); }
+                println!();
+            }
+        }
     }
     Ok(())
 }
 
-#[derive(Default)]
+#[derive(Default, Serialize)]
 struct Detail { base:f32, f_trait:f32, f_items:f32, f_stage:f32, f_hp:f32, f_syn:f32, f_conf:f32 }
 
+#[derive(Serialize)]
+struct ScoredOutput<'a> {
+    augment: &'a str,
+    score: f32,
+    mult: f32,
+    #[serde(flatten)]
+    detail: &'a Detail,
+}
+
 fn score_one(
     augment: &str,
     aug_db: &Augments,
@@ -122,7 +257,7 @@ fn score_one(
     state_traits: &HashMap<String,i32>,
     parts: &HashMap<String,i32>,
     taken: &Vec<String>,
-) -> (f32, f32, Detail) {
+) -> Result<(f32, f32, Detail)> {
     let mut det = Detail::default();
     let base = aug_db.base_scores.get(augment).map(|e| e.score).unwrap_or(60.0);
     det.base = base;
@@ -131,22 +266,49 @@ fn score_one(
     let prefer_traits = tags_to_prefer_traits(&tags, &traits_db.trait_groups);
 
     det.f_trait = proximity_to_next_tier(state_traits, &cfg.trait_breakpoints, &prefer_traits);
-    det.f_items = item_slam_bonus(parts, augment, &items_db.components);
+    det.f_items = item_slam_bonus(parts, augment, items_db);
     det.f_stage = stage_urgency(stage);
     det.f_hp    = hp_danger(hp);
     det.f_conf  = if taken.iter().any(|t| t==augment) { 1.0 } else { 0.0 };
     det.f_syn   = synergy_tag_bonus(&prefer_traits, state_traits);
 
     let w = &cfg.weights;
-    let mult = 1.0
-        + w.W_TRAIT * det.f_trait
-        + w.W_ITEMS * det.f_items
-        + w.W_STAGE * det.f_stage
-        + w.W_HP    * det.f_hp
-        + w.W_SYNERGY * det.f_syn
-        + w.W_CONFLICT * det.f_conf;
+    let mult = if let Some(formula) = &cfg.formula {
+        let ctx = formula_context(&det, hp, stage, w);
+        eval_formula(formula, &ctx)
+            .with_context(|| format!("Failed to evaluate configured scoring formula for '{}'", augment))?
+    } else {
+        1.0
+            + w.W_TRAIT * det.f_trait
+            + w.W_ITEMS * det.f_items
+            + w.W_STAGE * det.f_stage
+            + w.W_HP    * det.f_hp
+            + w.W_SYNERGY * det.f_syn
+            + w.W_CONFLICT * det.f_conf
+    };
+
+    Ok((base * mult, mult, det))
+}
 
-    (base * mult, mult, det)
+/// Builds the variable context a configured `formula` is evaluated against:
+/// every `Detail` factor, the raw `hp`, the parsed stage number, and each weight by name.
+fn formula_context(det: &Detail, hp: i32, stage: &str, w: &Weights) -> HashMap<String, f32> {
+    let mut ctx = HashMap::new();
+    ctx.insert("f_trait".to_string(), det.f_trait);
+    ctx.insert("f_items".to_string(), det.f_items);
+    ctx.insert("f_stage".to_string(), det.f_stage);
+    ctx.insert("f_hp".to_string(), det.f_hp);
+    ctx.insert("f_syn".to_string(), det.f_syn);
+    ctx.insert("f_conf".to_string(), det.f_conf);
+    ctx.insert("hp".to_string(), hp as f32);
+    ctx.insert("stage".to_string(), parse_stage_num(stage) as f32);
+    ctx.insert("W_TRAIT".to_string(), w.W_TRAIT);
+    ctx.insert("W_ITEMS".to_string(), w.W_ITEMS);
+    ctx.insert("W_STAGE".to_string(), w.W_STAGE);
+    ctx.insert("W_HP".to_string(), w.W_HP);
+    ctx.insert("W_CONFLICT".to_string(), w.W_CONFLICT);
+    ctx.insert("W_SYNERGY".to_string(), w.W_SYNERGY);
+    ctx
 }
 
 fn tags_to_prefer_traits(tags:&Vec<String>, groups:&HashMap<String, TraitGroup>) -> Vec<String> {
@@ -174,21 +336,30 @@ fn proximity_to_next_tier(traits:&HashMap<String,i32>, breaks:&HashMap<i32,i32>,
     bonus.clamp(0.0, 1.0)
 }
 
-fn item_slam_bonus(parts:&HashMap<String,i32>, augment:&str, slam:&HashMap<String,i32>) -> f32 {
+/// Generic, config-driven item-slam scorer: looks `augment` up in `items_db.slam_rules`
+/// and computes `sum(parts[component]*weight)/divisor` clamped to `[0,1]`. New
+/// item-centric augments are added purely by editing `items.yaml`.
+fn item_slam_bonus(parts:&HashMap<String,i32>, augment:&str, items_db:&ItemsDb) -> f32 {
     let aug = augment.to_lowercase();
-    if ["component grab bag","portable forge","pandora’s items","pandoras items","pandora's items"].iter().any(|a| aug==*a) {
-        let raw:i32 = slam.iter().map(|(k,v)| parts.get(k).unwrap_or(&0) * v).sum();
-        return (raw as f32 / 20.0).clamp(0.0, 1.0)
+    let rule = match items_db.slam_rules.iter().find(|(name, _)| name.to_lowercase() == aug) {
+        Some((_, r)) => r,
+        None => return 0.0,
+    };
+    if rule.divisor == 0.0 {
+        return 0.0;
     }
-    if ["sunfire board","exiles","triumphant return"].iter().any(|a| aug==*a) {
-        let raw = parts.get("Belt").unwrap_or(&0)*10 + parts.get("Chain").unwrap_or(&0)*9;
-        return (raw as f32 / 15.0).clamp(0.0, 1.0)
-    }
-    0.0
+    let raw: f32 = rule.parts.iter()
+        .map(|p| *parts.get(&p.component).unwrap_or(&0) as f32 * p.weight)
+        .sum();
+    (raw / rule.divisor).clamp(0.0, 1.0)
+}
+
+fn parse_stage_num(stage:&str) -> i32 {
+    stage.split('-').next().and_then(|x| x.parse::<i32>().ok()).unwrap_or(2)
 }
 
 fn stage_urgency(stage:&str) -> f32 {
-    let s = stage.split('-').next().and_then(|x| x.parse::<i32>().ok()).unwrap_or(2);
+    let s = parse_stage_num(stage);
     (((s - 2) as f32) / 4.0).clamp(0.0, 1.0)
 }
 
@@ -214,3 +385,210 @@ fn load_yaml<T: for<'de> serde::Deserialize<'de>>(path:String) -> Result<T> {
         .with_context(|| format!("Failed to parse YAML {}", path))?;
     Ok(val)
 }
+
+const FUZZY_MAX_DISTANCE: usize = 3;
+const FUZZY_MAX_SUGGESTIONS: usize = 3;
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in dp.iter_mut().enumerate() { row[0] = i; }
+    for j in 0..=b.len() { dp[0][j] = j; }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i-1] == b[j-1] { 0 } else { 1 };
+            dp[i][j] = (dp[i-1][j] + 1).min(dp[i][j-1] + 1).min(dp[i-1][j-1] + cost);
+        }
+    }
+    dp[a.len()][b.len()]
+}
+
+/// Up to `FUZZY_MAX_SUGGESTIONS` known names within `FUZZY_MAX_DISTANCE` edits of `name`, closest first.
+fn closest_matches<'a>(name: &str, candidates: impl Iterator<Item = &'a String>) -> Vec<&'a str> {
+    let needle = name.to_lowercase();
+    let mut scored: Vec<(usize, &str)> = candidates
+        .map(|c| (levenshtein(&needle, &c.to_lowercase()), c.as_str()))
+        .filter(|(dist, _)| *dist <= FUZZY_MAX_DISTANCE)
+        .collect();
+    scored.sort_by_key(|(dist, _)| *dist);
+    scored.into_iter().take(FUZZY_MAX_SUGGESTIONS).map(|(_, c)| c).collect()
+}
+
+/// Cross-checks `names` (sourced from `flag`, used only for the warning text) against
+/// `aug_db`'s known augments, suggesting close matches by edit distance for each miss.
+fn validate_augment_names(flag: &str, names: &[String], aug_db: &Augments) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for name in names {
+        if aug_db.base_scores.contains_key(name) {
+            continue;
+        }
+        let suggestions = closest_matches(name, aug_db.base_scores.keys());
+        if suggestions.is_empty() {
+            warnings.push(format!("{}: unknown augment '{}' (no close matches found)", flag, name));
+        } else {
+            warnings.push(format!("{}: unknown augment '{}' — did you mean: {}?", flag, name, suggestions.join(", ")));
+        }
+    }
+    warnings
+}
+
+/// Every tag referenced by an `AugEntry` that doesn't resolve to a `trait_groups` key.
+fn validate_tags(aug_db: &Augments, traits_db: &TraitsDb) -> Vec<String> {
+    let mut unresolved: Vec<&str> = aug_db.base_scores.values()
+        .flat_map(|e| e.tags.iter())
+        .filter(|t| !traits_db.trait_groups.contains_key(t.as_str()))
+        .map(|t| t.as_str())
+        .collect();
+    unresolved.sort_unstable();
+    unresolved.dedup();
+    unresolved.into_iter()
+        .map(|t| format!("unresolved trait tag '{}' (no matching trait_groups entry)", t))
+        .collect()
+}
+
+// --- Tiny arithmetic DSL for `config.yaml`'s optional `formula` ---------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f32),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>> {
+    let mut out = Vec::new();
+    let chars: Vec<char> = src.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' | '\n' | '\r' => { i += 1; }
+            '+' => { out.push(Token::Plus); i += 1; }
+            '-' => { out.push(Token::Minus); i += 1; }
+            '*' => { out.push(Token::Star); i += 1; }
+            '/' => { out.push(Token::Slash); i += 1; }
+            '(' => { out.push(Token::LParen); i += 1; }
+            ')' => { out.push(Token::RParen); i += 1; }
+            ',' => { out.push(Token::Comma); i += 1; }
+            _ if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') { i += 1; }
+                let text: String = chars[start..i].iter().collect();
+                let n = text.parse::<f32>()
+                    .with_context(|| format!("Invalid number literal '{}' in formula", text))?;
+                out.push(Token::Num(n));
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') { i += 1; }
+                out.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            _ => bail!("Unexpected character '{}' in formula", c),
+        }
+    }
+    Ok(out)
+}
+
+fn binop_prec(t: &Token) -> Option<u8> {
+    match t {
+        Token::Plus | Token::Minus => Some(1),
+        Token::Star | Token::Slash => Some(2),
+        _ => None,
+    }
+}
+
+struct FormulaParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    ctx: &'a HashMap<String, f32>,
+}
+
+impl<'a> FormulaParser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    /// Precedence-climbing: read a primary, then fold in binary operators
+    /// whose precedence is `>= min_prec`, recursing with `op_prec + 1` to
+    /// keep `+ - * /` left-associative.
+    fn parse_expr(&mut self, min_prec: u8) -> Result<f32> {
+        let mut lhs = self.parse_unary()?;
+        while let Some(op) = self.peek().cloned() {
+            let prec = match binop_prec(&op) {
+                Some(p) if p >= min_prec => p,
+                _ => break,
+            };
+            self.bump();
+            let rhs = self.parse_expr(prec + 1)?;
+            lhs = match op {
+                Token::Plus => lhs + rhs,
+                Token::Minus => lhs - rhs,
+                Token::Star => lhs * rhs,
+                Token::Slash => {
+                    ensure!(rhs != 0.0, "Division by zero in formula");
+                    lhs / rhs
+                }
+                _ => unreachable!(),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<f32> {
+        if matches!(self.peek(), Some(Token::Minus)) {
+            self.bump();
+            return Ok(-self.parse_unary()?);
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<f32> {
+        match self.bump() {
+            Some(Token::Num(n)) => Ok(n),
+            Some(Token::Ident(name)) if matches!(self.peek(), Some(Token::LParen)) => {
+                self.bump(); // consume '('
+                let a = self.parse_expr(0)?;
+                ensure!(matches!(self.peek(), Some(Token::Comma)), "Expected ',' in call to '{}' in formula", name);
+                self.bump();
+                let b = self.parse_expr(0)?;
+                ensure!(matches!(self.peek(), Some(Token::RParen)), "Expected ')' closing call to '{}' in formula", name);
+                self.bump();
+                match name.as_str() {
+                    "min" => Ok(a.min(b)),
+                    "max" => Ok(a.max(b)),
+                    other => bail!("Unknown function '{}' in formula", other),
+                }
+            }
+            Some(Token::Ident(name)) => self.ctx.get(&name).copied()
+                .with_context(|| format!("Unknown identifier '{}' in formula", name)),
+            Some(Token::LParen) => {
+                let v = self.parse_expr(0)?;
+                ensure!(matches!(self.peek(), Some(Token::RParen)), "Expected closing ')' in formula");
+                self.bump();
+                Ok(v)
+            }
+            other => bail!("Unexpected token {:?} in formula", other),
+        }
+    }
+}
+
+fn eval_formula(src: &str, ctx: &HashMap<String, f32>) -> Result<f32> {
+    let tokens = tokenize(src)?;
+    let mut parser = FormulaParser { tokens: &tokens, pos: 0, ctx };
+    let value = parser.parse_expr(0)?;
+    ensure!(parser.pos == tokens.len(), "Unexpected trailing tokens in formula");
+    Ok(value)
+}